@@ -1,10 +1,10 @@
-//! 设置编译平台
+//! 设置编译平台. CPU/内存的采集已经通过 `SystemProbe` 做了跨平台兜底(见 `src/probe`), 磁盘信息由 `sysinfo` 采集,
+//! 所以这里不再按平台拦截编译, 只是给出提示, 方便在非 Linux 平台上确认落到了哪个分支。
 
 fn main() {
     if cfg!(target_os = "linux") {
-        println!("info: Build on Linux!");
+        println!("info: Build on Linux, using /proc-based collection.");
     } else {
-        println!("warning: This project can only be built on Linux!");
-        std::process::exit(1);
+        println!("info: Build on a non-Linux target, falling back to sysinfo-based collection.");
     }
 }
\ No newline at end of file
@@ -0,0 +1,44 @@
+//! 固定大小的滑动窗口, 用于保留最近若干次采样并渲染为终端火花线(sparkline)
+//! 不绑定具体指标, CPU 总使用率、每个核心的使用率、内存使用率等序列都可以复用这套窗口机制
+
+use std::collections::VecDeque;
+
+pub const WINDOW_SIZE: usize = 60;
+
+const SPARK_GLYPHS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// 固定大小的环形窗口, 保留最近 `WINDOW_SIZE` 个采样值, 写满后新样本会挤掉最旧的样本
+#[derive(Debug, Clone)]
+pub struct History {
+    samples: VecDeque<f64>,
+}
+
+impl Default for History {
+    fn default() -> Self {
+        Self { samples: VecDeque::with_capacity(WINDOW_SIZE) }
+    }
+}
+
+impl History {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 写入一个新样本, 超出窗口大小时挤掉最旧的样本
+    pub fn push(&mut self, value: f64) {
+        if self.samples.len() == WINDOW_SIZE {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(value);
+    }
+
+    /// 渲染为终端火花线: 把 0~100 的值按 8 档分桶, 映射成对应的方块字符
+    pub fn render_sparkline(&self) -> String {
+        self.samples.iter().map(|&value| Self::glyph_for(value)).collect()
+    }
+
+    fn glyph_for(value: f64) -> char {
+        let level = ((value / 100.0) * SPARK_GLYPHS.len() as f64) as usize;
+        SPARK_GLYPHS[level.min(SPARK_GLYPHS.len() - 1)]
+    }
+}
@@ -1,25 +1,61 @@
-use std::{thread};
-use std::time::Duration;
+use std::{env, thread};
+use std::time::{Duration, Instant};
+use crate::history::History;
+use crate::network::Network;
 use crate::prepare::{to_result};
-use crate::stat::{Stat};
+use crate::probe::get_probe;
+use crate::stat::Stat;
 
 mod stat;
 mod error;
 
 mod prepare;
 mod monitor;
+mod thermal;
+mod power;
+mod network;
+mod history;
+mod probe;
 
 fn main() {
-    let mut prev_stat = Stat::read_proc_stat().unwrap();
+    // `--sparkline` 时打印 CPU 使用率的终端火花线, 而不是整段 JSON
+    let sparkline_mode = env::args().any(|arg| arg == "--sparkline");
+    // `--skip-loopback` 时网卡流量统计不包含回环接口 `lo`
+    let skip_loopback = env::args().any(|arg| arg == "--skip-loopback");
+
+    let mut probe = get_probe();
+
+    // `/proc/stat`、`/proc/net/dev` 只在 Linux 下可读, 其它平台上这些扩展指标保持为 None
+    let mut prev_stat = if cfg!(target_os = "linux") { Stat::read_proc_stat().ok() } else { None };
+    let mut prev_net_stat = if cfg!(target_os = "linux") { Network::read_proc_net_dev().ok() } else { None };
+    let mut prev_instant = Instant::now();
+    let mut cpu_history = History::new();
+
     loop {
         thread::sleep(Duration::from_secs(1));
-        let current_stat = Stat::read_proc_stat().unwrap();
+        let current_stat = if cfg!(target_os = "linux") { Stat::read_proc_stat().ok() } else { None };
+        let current_net_stat = if cfg!(target_os = "linux") { Network::read_proc_net_dev().ok() } else { None };
+        let elapsed_secs = prev_instant.elapsed().as_secs_f64();
 
-        let cpu_usage = Stat::calculate_cpu_usage(current_stat.clone(), prev_stat.clone());
-        // println!("CPU Usage: {:.2}%", cpu_usage);
+        if sparkline_mode {
+            let cpu_usage = probe.get_cpu_info(current_stat.as_ref(), prev_stat.as_ref()).map(|info| info.usage).unwrap_or(0.0);
+            cpu_history.push(cpu_usage);
+            println!("cpu {:>6.2}% {}", cpu_usage, cpu_history.render_sparkline());
+        } else {
+            let result = Stat::get_system_info(
+                probe.as_mut(),
+                current_stat.as_ref(),
+                prev_stat.as_ref(),
+                current_net_stat.as_ref(),
+                prev_net_stat.as_ref(),
+                elapsed_secs,
+                skip_loopback,
+            );
+            println!("system info: {}", to_result(result));
+        }
 
-        let result = Stat::get_system_info(cpu_usage);
-        println!("system info: {}", to_result(result));
-        prev_stat = current_stat.clone();
+        prev_stat = current_stat;
+        prev_net_stat = current_net_stat;
+        prev_instant = Instant::now();
     }
 }
@@ -0,0 +1,86 @@
+//! 读取 linux 下的 `/proc/net/dev` 文件, 获取网卡累计流量并计算收发速率
+
+use std::fs;
+use std::io;
+use serde::{Deserialize, Serialize};
+
+/// 一次采样中某个网卡的累计计数器
+#[derive(Debug, Clone, Default)]
+pub struct NetCounter {
+    pub(crate) name: String,
+    pub(crate) rx_bytes: u64,
+    pub(crate) tx_bytes: u64,
+}
+
+/// `/proc/net/dev` 的一次完整采样
+#[derive(Debug, Clone, Default)]
+pub struct NetStat {
+    pub(crate) interfaces: Vec<NetCounter>,
+}
+
+/// 对外暴露的网卡流量信息, 包含累计字节数与收发速率
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct NetInterface {
+    pub name: String,
+    pub rx_bytes: u64,
+    pub tx_bytes: u64,
+    pub rx_rate: f64, // 接收速率, 单位 字节/秒
+    pub tx_rate: f64, // 发送速率, 单位 字节/秒
+}
+
+pub struct Network;
+
+impl Network {
+    /// 读取 `/proc/net/dev`, 接收字节/包为第 1~2 列, 发送字节/包为第 9~10 列(接口名后)
+    pub(crate) fn read_proc_net_dev() -> Result<NetStat, io::Error> {
+        let contents = fs::read_to_string("/proc/net/dev")?;
+
+        let interfaces = contents
+            .lines()
+            .skip(2) // 前两行是表头
+            .filter_map(Self::parse_line)
+            .collect();
+
+        Ok(NetStat { interfaces })
+    }
+
+    fn parse_line(line: &str) -> Option<NetCounter> {
+        let (name, rest) = line.split_once(':')?;
+        let fields: Vec<&str> = rest.split_whitespace().collect();
+        if fields.len() < 10 {
+            return None;
+        }
+
+        Some(NetCounter {
+            name: name.trim().to_string(),
+            rx_bytes: fields[0].parse().ok()?,
+            tx_bytes: fields[8].parse().ok()?,
+        })
+    }
+
+    /// 按网卡名匹配前后两次采样, 计算收发速率; `skip_loopback` 为 true 时跳过 `lo`,
+    /// 网卡在两次采样之间增删也能正常处理(按名字匹配, 找不到上次采样时速率记为 0)
+    pub(crate) fn calculate_rates(current: &NetStat, prev: &NetStat, elapsed_secs: f64, skip_loopback: bool) -> Vec<NetInterface> {
+        current.interfaces.iter()
+            .filter(|cur| !skip_loopback || cur.name != "lo")
+            .map(|cur| {
+                let prev_counter = prev.interfaces.iter().find(|p| p.name == cur.name);
+                let (rx_rate, tx_rate) = match prev_counter {
+                    Some(prev_counter) if elapsed_secs > 0.0 => (
+                        cur.rx_bytes.saturating_sub(prev_counter.rx_bytes) as f64 / elapsed_secs,
+                        cur.tx_bytes.saturating_sub(prev_counter.tx_bytes) as f64 / elapsed_secs,
+                    ),
+                    _ => (0.0, 0.0),
+                };
+
+                NetInterface {
+                    name: cur.name.clone(),
+                    rx_bytes: cur.rx_bytes,
+                    tx_bytes: cur.tx_bytes,
+                    rx_rate,
+                    tx_rate,
+                }
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,61 @@
+//! 读取 linux 下的 `/sys/class/power_supply` 目录, 获取电池及电源信息
+
+use std::fs;
+use serde::{Deserialize, Serialize};
+
+/// 单个电源(电池或市电)的信息
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct PowerSupply {
+    pub name: String,              // `power_supply` 目录名, 如 `BAT0`、`AC`
+    pub type_: String,             // `type` 文件内容, 如 `Battery`、`Mains`
+    pub capacity: Option<u8>,      // 电量百分比, 仅电池有效
+    pub status: String,            // `status` 文件内容, 如 `Charging`、`Discharging`、`Full`
+    pub voltage_now: Option<u64>,  // 当前电压, 单位 µV
+    pub model_name: String,        // `model_name` 或 `manufacturer`
+}
+
+pub struct Power;
+
+impl Power {
+    const POWER_SUPPLY_DIR: &'static str = "/sys/class/power_supply";
+
+    /// 获取所有电源信息, 桌面机没有电池时返回空列表
+    pub(crate) fn get_power_supplies() -> Vec<PowerSupply> {
+        let entries = match fs::read_dir(Self::POWER_SUPPLY_DIR) {
+            Ok(entries) => entries,
+            Err(_) => return Vec::new(),
+        };
+
+        entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| Self::read_supply(entry.path()))
+            .collect()
+    }
+
+    /// 读取单个电源目录
+    fn read_supply(path: std::path::PathBuf) -> PowerSupply {
+        let name = path.file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or("")
+            .to_string();
+
+        let model_name = Self::read_attr(&path, "model_name")
+            .filter(|value| !value.is_empty())
+            .or_else(|| Self::read_attr(&path, "manufacturer"))
+            .unwrap_or_default();
+
+        PowerSupply {
+            name,
+            type_: Self::read_attr(&path, "type").unwrap_or_default(),
+            capacity: Self::read_attr(&path, "capacity").and_then(|value| value.parse().ok()),
+            status: Self::read_attr(&path, "status").unwrap_or_default(),
+            voltage_now: Self::read_attr(&path, "voltage_now").and_then(|value| value.parse().ok()),
+            model_name,
+        }
+    }
+
+    /// 读取电源目录下的某个属性文件, 并去掉首尾空白
+    fn read_attr(path: &std::path::Path, attr: &str) -> Option<String> {
+        fs::read_to_string(path.join(attr)).ok().map(|value| value.trim().to_string())
+    }
+}
@@ -0,0 +1,81 @@
+//! 跨平台的 CPU/内存采集层. Linux 下沿用 `/proc` 的既有读法, 其它平台(macOS、Windows、FreeBSD 等)
+//! 用 `sysinfo` 兜底, 由 `get_probe` 在运行时根据目标平台选择具体实现。
+
+use sysinfo::{CpuExt, System, SystemExt};
+use crate::stat::{CpuInfo, MemInfo, ProcStat, Stat};
+
+/// 采集 CPU、内存基础信息的统一接口. `current_stat`/`prev_stat` 是调用方已经读好的 `/proc/stat`
+/// 采样(主循环里 `kernel_stat` 也在用同一份), 避免每个子系统各自重复读取、各自维护"上一次采样"
+pub(crate) trait SystemProbe {
+    fn get_cpu_info(&mut self, current_stat: Option<&ProcStat>, prev_stat: Option<&ProcStat>) -> Result<CpuInfo, String>;
+    fn get_mem_info(&mut self) -> Result<MemInfo, String>;
+}
+
+/// Linux 实现: 核心数、频率等静态信息来自 `/proc/cpuinfo`、`/proc/meminfo`,
+/// 使用率由调用方传入的前后两次 `/proc/stat` 采样做差值计算(含每个逻辑核心), 自身不持有状态
+pub(crate) struct LinuxProbe;
+
+impl LinuxProbe {
+    pub(crate) fn new() -> Self {
+        Self
+    }
+}
+
+impl SystemProbe for LinuxProbe {
+    fn get_cpu_info(&mut self, current_stat: Option<&ProcStat>, prev_stat: Option<&ProcStat>) -> Result<CpuInfo, String> {
+        let mut info = Stat::get_cup_info()?;
+
+        if let (Some(current_stat), Some(prev_stat)) = (current_stat, prev_stat) {
+            info.usage = Stat::calculate_cpu_usage(&current_stat.aggregate, &prev_stat.aggregate);
+            info.per_core_usage = Stat::calculate_per_core_usage(current_stat, prev_stat);
+        }
+
+        Ok(info)
+    }
+
+    fn get_mem_info(&mut self) -> Result<MemInfo, String> {
+        Stat::get_mem_info()
+    }
+}
+
+/// 其它平台的兜底实现, 基于 `sysinfo` 的跨平台 CPU/内存采集
+pub(crate) struct SysinfoProbe {
+    sys: System,
+}
+
+impl SysinfoProbe {
+    pub(crate) fn new() -> Self {
+        Self { sys: System::new_all() }
+    }
+}
+
+impl SystemProbe for SysinfoProbe {
+    fn get_cpu_info(&mut self, _current_stat: Option<&ProcStat>, _prev_stat: Option<&ProcStat>) -> Result<CpuInfo, String> {
+        self.sys.refresh_cpu();
+        let cpus = self.sys.cpus();
+
+        Ok(CpuInfo {
+            physics_core_num: self.sys.physical_core_count().unwrap_or(0) as u64,
+            virtual_core_num: cpus.len() as u64,
+            usage: self.sys.global_cpu_info().cpu_usage() as f64,
+            per_core_usage: cpus.iter().map(|cpu| cpu.cpu_usage() as f64).collect(),
+        })
+    }
+
+    fn get_mem_info(&mut self) -> Result<MemInfo, String> {
+        self.sys.refresh_memory();
+        Ok(MemInfo {
+            mem_total: self.sys.total_memory(),
+            mem_available: self.sys.available_memory(),
+        })
+    }
+}
+
+/// 按运行时平台选择合适的采集实现
+pub(crate) fn get_probe() -> Box<dyn SystemProbe> {
+    if cfg!(target_os = "linux") {
+        Box::new(LinuxProbe::new())
+    } else {
+        Box::new(SysinfoProbe::new())
+    }
+}
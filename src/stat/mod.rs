@@ -5,13 +5,17 @@ use std::{io, thread};
 use std::fs::File;
 use std::io::Read;
 use std::process::Command;
-use std::time::Duration;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use procfs::{Current, Meminfo};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use crate::error::Error;
 use crate::monitor::{Monitor, Os, OsDisk};
+use crate::network::{NetInterface, NetStat, Network};
+use crate::power::{Power, PowerSupply};
 use crate::prepare::{get_error_response, get_success_response, HttpResponse};
+use crate::probe::SystemProbe;
+use crate::thermal::{Thermal, ThermalZone};
 
 #[derive(Default, Debug, Clone, Serialize, Deserialize)]
 pub struct SystemInfo {
@@ -22,6 +26,14 @@ pub struct SystemInfo {
     pub(crate) cpu_info: CpuInfo,
     #[serde(rename = "memInfo")]
     pub(crate) mem_info: MemInfo,
+    #[serde(rename = "thermalZones")]
+    pub(crate) thermal_zones: Vec<ThermalZone>, // 各温控区温度
+    #[serde(rename = "powerSupplies")]
+    pub(crate) power_supplies: Vec<PowerSupply>, // 电池及电源信息, 台式机通常为空
+    #[serde(rename = "netInterfaces")]
+    pub(crate) net_interfaces: Vec<NetInterface>, // 网卡流量及速率
+    #[serde(rename = "kernelStat")]
+    pub(crate) kernel_stat: KernelStat, // ctxt、processes、中断等内核计数器
     #[serde(rename = "homeDir")]
     home_dir: String,       // 用户主目录
 }
@@ -39,6 +51,18 @@ pub struct CpuInfo {
     pub physics_core_num: u64, // 物理核心数
     pub virtual_core_num: u64, // 虚拟核心数(包括 `超线程技术（Hyper-Threading）导致的虚拟核心`)
     pub usage: f64, // 使用率
+    pub per_core_usage: Vec<f64>, // 每个逻辑核心的使用率, 按当前采样中 `cpuN` 行的顺序排列; 上一次采样中没有的核心不计入, 此时下标不一定等于 N
+}
+
+/// `/proc/stat` 中除 CPU 时间以外的内核计数器
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct KernelStat {
+    pub procs_running: u64, // 正在运行的进程数(瞬时值)
+    pub procs_blocked: u64, // 被阻塞的进程数(瞬时值)
+    pub uptime_secs: u64, // 系统运行时长, 由 `btime` 与当前时间推算
+    pub ctxt_per_sec: f64, // 上下文切换速率
+    pub processes_per_sec: f64, // fork 速率
+    pub interrupts_per_sec: f64, // 中断速率
 }
 
 
@@ -46,8 +70,18 @@ pub struct Stat;
 
 impl Stat {
 
-    /// 获取系统信息
-    pub(crate) fn get_system_info(cpu_usage: f64) -> HttpResponse {
+    /// 获取系统信息. CPU、内存通过 `probe` 采集(Linux 下读 `/proc`, 其它平台用 `sysinfo` 兜底);
+    /// 其余基于 `/proc`、`/sys` 的扩展指标(网卡速率、内核计数器)目前仍只在 Linux 下采集,
+    /// 在其它平台上对应的采样参数传 `None` 即可, 保持默认空值。
+    pub(crate) fn get_system_info(
+        probe: &mut dyn SystemProbe,
+        current_stat: Option<&ProcStat>,
+        prev_stat: Option<&ProcStat>,
+        net_current: Option<&NetStat>,
+        net_prev: Option<&NetStat>,
+        elapsed_secs: f64,
+        skip_loopback: bool,
+    ) -> HttpResponse {
         let mut system_info = SystemInfo::default();
         let mut monitor = Monitor::new();
 
@@ -59,7 +93,7 @@ impl Stat {
         let disk_list = monitor.get_all_disk_info();
         system_info.disk_list = disk_list;
 
-        let cpu_info = match Self::get_cup_info() {
+        let cpu_info = match probe.get_cpu_info(current_stat, prev_stat) {
             Ok(info) => {
                 info
             }
@@ -69,9 +103,8 @@ impl Stat {
         };
 
         system_info.cpu_info = cpu_info;
-        system_info.cpu_info.usage = cpu_usage;
 
-        let mem_info = match Self::get_mem_info() {
+        let mem_info = match probe.get_mem_info() {
             Ok(info) => {
                 info
             }
@@ -82,6 +115,18 @@ impl Stat {
 
         system_info.mem_info = mem_info;
 
+        system_info.thermal_zones = Thermal::get_thermal_zones();
+
+        system_info.power_supplies = Power::get_power_supplies();
+
+        if let (Some(net_current), Some(net_prev)) = (net_current, net_prev) {
+            system_info.net_interfaces = Network::calculate_rates(net_current, net_prev, elapsed_secs, skip_loopback);
+        }
+
+        if let (Some(current_stat), Some(prev_stat)) = (current_stat, prev_stat) {
+            system_info.kernel_stat = Self::build_kernel_stat(current_stat, prev_stat, elapsed_secs);
+        }
+
         let home_dir = Self::get_user_home_dir();
         system_info.home_dir = home_dir;
         let data = serde_json::to_value(&system_info).unwrap_or(Value::default());
@@ -133,7 +178,7 @@ impl Stat {
     }
 
     /// 获取内存使用情况
-    fn get_mem_info() -> Result<MemInfo, String> {
+    pub(crate) fn get_mem_info() -> Result<MemInfo, String> {
         let mem_info = Meminfo::current().map_err(|err| Error::Error(err.to_string()).to_string())?;
         let mem_total = mem_info.mem_total;
         let mem_available = mem_info.mem_available.unwrap_or(0);
@@ -143,7 +188,7 @@ impl Stat {
         })
     }
 
-    fn get_cup_info() -> Result<CpuInfo, String> {
+    pub(crate) fn get_cup_info() -> Result<CpuInfo, String> {
         let cup_info = procfs::CpuInfo::current().map_err(|err| Error::convert_string(err.to_string().as_str()))?;
         let fields = cup_info.fields.clone();
         let cpus = cup_info.cpus.clone();
@@ -196,8 +241,9 @@ impl Stat {
      5. CPU 使用率 = 使用时间 / 总时间 * 100% = used / total * 100%
  */
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct CpuUsageInfo {
+    label: String, // 行首标签, 如 `cpu`(汇总) 或 `cpu0`、`cpu1`(单核)
     user: u64,
     nice: u64,
     system: u64,
@@ -216,6 +262,19 @@ impl CpuUsageInfo {
     }
 }
 
+/// `/proc/stat` 的一次完整采样: 汇总 CPU 时间、每个逻辑核心(`cpuN`)的时间, 以及其它内核计数器
+#[derive(Debug, Clone, Default)]
+pub struct ProcStat {
+    pub(crate) aggregate: CpuUsageInfo,
+    pub(crate) per_core: Vec<CpuUsageInfo>,
+    pub(crate) ctxt: u64,          // 开机以来的上下文切换总数
+    pub(crate) processes: u64,     // 开机以来的 fork 总数
+    pub(crate) procs_running: u64, // 正在运行的进程数
+    pub(crate) procs_blocked: u64, // 被阻塞的进程数
+    pub(crate) btime: u64,         // 开机时间, Unix 时间戳(秒)
+    pub(crate) intr_total: u64,    // 开机以来的中断总数(`intr` 行的第一个字段)
+}
+
 impl Stat {
 
     /// 获取 CPU 信息
@@ -226,22 +285,64 @@ impl Stat {
             thread::sleep(Duration::from_secs(1));
             let current_stat = Self::read_proc_stat().unwrap();
 
-            let cpu_usage = Self::calculate_cpu_usage(prev_stat, current_stat.clone());
+            let cpu_usage = Self::calculate_cpu_usage(&current_stat.aggregate, &prev_stat.aggregate);
             println!("CPU Usage: {:.2}%", cpu_usage);
-            prev_stat = current_stat.clone();
+            prev_stat = current_stat;
         }
     }
 
-    /// 读取 `/proc/stat`
-    pub(crate) fn read_proc_stat() -> Result<CpuUsageInfo, io::Error> {
+    /// 读取 `/proc/stat`, 汇总行(`cpu `)与每个逻辑核心行(`cpu0`、`cpu1`、...)都会被解析
+    pub(crate) fn read_proc_stat() -> Result<ProcStat, io::Error> {
         let mut file = File::open("/proc/stat")?;
         let mut contents = String::new();
         file.read_to_string(&mut contents)?;
 
         let lines: Vec<&str> = contents.lines().collect();
-        let cpu_line = lines.iter().find(|line| line.starts_with("cpu ")).unwrap();
-        let fields: Vec<&str> = cpu_line.split_whitespace().collect();
+        let aggregate_line = lines.iter().find(|line| line.starts_with("cpu ")).unwrap();
+        let aggregate = Self::parse_cpu_line(aggregate_line)?;
+
+        let mut per_core = Vec::new();
+        let mut ctxt = 0;
+        let mut processes = 0;
+        let mut procs_running = 0;
+        let mut procs_blocked = 0;
+        let mut btime = 0;
+        let mut intr_total = 0;
+
+        for line in lines.iter() {
+            if Self::is_per_core_line(line) {
+                per_core.push(Self::parse_cpu_line(line)?);
+            } else if let Some(value) = line.strip_prefix("ctxt ") {
+                ctxt = value.trim().parse().unwrap_or(0);
+            } else if let Some(value) = line.strip_prefix("processes ") {
+                processes = value.trim().parse().unwrap_or(0);
+            } else if let Some(value) = line.strip_prefix("procs_running ") {
+                procs_running = value.trim().parse().unwrap_or(0);
+            } else if let Some(value) = line.strip_prefix("procs_blocked ") {
+                procs_blocked = value.trim().parse().unwrap_or(0);
+            } else if let Some(value) = line.strip_prefix("btime ") {
+                btime = value.trim().parse().unwrap_or(0);
+            } else if let Some(value) = line.strip_prefix("intr ") {
+                intr_total = value.split_whitespace().next().and_then(|v| v.parse().ok()).unwrap_or(0);
+            }
+        }
 
+        Ok(ProcStat { aggregate, per_core, ctxt, processes, procs_running, procs_blocked, btime, intr_total })
+    }
+
+    /// 判断是否是单个逻辑核心的行, 即 `cpu` 后紧跟数字(`cpu0`、`cpu1`...), 排除汇总行 `cpu `
+    fn is_per_core_line(line: &str) -> bool {
+        line.strip_prefix("cpu")
+            .and_then(|rest| rest.chars().next())
+            .map(|c| c.is_ascii_digit())
+            .unwrap_or(false)
+    }
+
+    /// 解析 `/proc/stat` 中以 `cpu` 开头的一行
+    fn parse_cpu_line(line: &str) -> Result<CpuUsageInfo, io::Error> {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+
+        let label = fields[0].to_string();
         let user: u64 = fields[1].parse().unwrap();
         let nice: u64 = fields[2].parse().unwrap();
         let system: u64 = fields[3].parse().unwrap();
@@ -254,6 +355,7 @@ impl Stat {
         let guest_nice: u64 = fields[10].parse().unwrap();
 
         Ok(CpuUsageInfo {
+            label,
             user,
             nice,
             system,
@@ -268,26 +370,27 @@ impl Stat {
     }
 
     /// 计算
-    pub(crate) fn calculate_cpu_usage(current: CpuUsageInfo, prev: CpuUsageInfo) -> f64 {
+    pub(crate) fn calculate_cpu_usage(current: &CpuUsageInfo, prev: &CpuUsageInfo) -> f64 {
         // println!("current: {:?}", current);
         // println!("prev: {:?}", prev);
 
         // 1. 计算两次的 CPU 总时间
-        let current_total_time = CpuUsageInfo::get_total_time(&current); // 当前总时间
-        let prev_total_time = CpuUsageInfo::get_total_time(&prev); // 前一个时间段的总时间
+        let current_total_time = CpuUsageInfo::get_total_time(current); // 当前总时间
+        let prev_total_time = CpuUsageInfo::get_total_time(prev); // 前一个时间段的总时间
         // println!("current_total_time: {}", current_total_time);
         //  println!("prev_total_time: {}", prev_total_time);
 
-        // 2. 计算两次的 CPU 剩余时间
-        let left_time = current.idle - prev.idle;
+        // 2. 计算两次的 CPU 剩余时间. 核心被下线再上线后 `cpuN` 计数器会从 0 重新累计,
+        // 此时 current < prev, 用 saturating_sub 避免下溢(而不是 panic 或在 release 下算出离谱的使用率)
+        let left_time = current.idle.saturating_sub(prev.idle);
         // println!("left_time: {}", left_time);
 
         // 3. 计算两次的 CPU 使用时间
-        let usage_time = (current_total_time - prev_total_time) - left_time;
+        let usage_time = current_total_time.saturating_sub(prev_total_time).saturating_sub(left_time);
         // println!("usage_time: {}", usage_time);
 
         // 4. 总时间
-        let usage_total_time = current_total_time - prev_total_time;
+        let usage_total_time = current_total_time.saturating_sub(prev_total_time);
         // println!("usage_total_time: {}", usage_total_time);
 
         // 4. 计算 CPU 使用率: usage_time/usage_total_time
@@ -301,4 +404,36 @@ impl Stat {
 
         return 0.0;
     }
+
+    /// 按 `cpuN` 标签匹配前后两次采样, 分别计算每个逻辑核心的使用率
+    pub(crate) fn calculate_per_core_usage(current: &ProcStat, prev: &ProcStat) -> Vec<f64> {
+        current.per_core.iter().filter_map(|cur_core| {
+            prev.per_core.iter()
+                .find(|prev_core| prev_core.label == cur_core.label)
+                .map(|prev_core| Self::calculate_cpu_usage(cur_core, prev_core))
+        }).collect()
+    }
+
+    /// 汇总 `/proc/stat` 的其它内核计数器: 瞬时值直接使用当前采样, 速率值按前后两次采样做增量计算
+    pub(crate) fn build_kernel_stat(current: &ProcStat, prev: &ProcStat, elapsed_secs: f64) -> KernelStat {
+        let now_secs = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+        let uptime_secs = now_secs.saturating_sub(current.btime);
+
+        let rate_per_sec = |current: u64, prev: u64| -> f64 {
+            if elapsed_secs > 0.0 {
+                current.saturating_sub(prev) as f64 / elapsed_secs
+            } else {
+                0.0
+            }
+        };
+
+        KernelStat {
+            procs_running: current.procs_running,
+            procs_blocked: current.procs_blocked,
+            uptime_secs,
+            ctxt_per_sec: rate_per_sec(current.ctxt, prev.ctxt),
+            processes_per_sec: rate_per_sec(current.processes, prev.processes),
+            interrupts_per_sec: rate_per_sec(current.intr_total, prev.intr_total),
+        }
+    }
 }
\ No newline at end of file
@@ -0,0 +1,49 @@
+//! 读取 linux 下的 `/sys/class/thermal` 目录, 获取各个温控区(SoC、GPU、主板、每个 CPU 核心簇等)的温度
+
+use std::fs;
+use serde::{Deserialize, Serialize};
+
+/// 单个温控区的读数
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct ThermalZone {
+    pub index: usize,      // `thermal_zoneN` 的序号
+    pub label: String,     // `type` 文件内容, 如 `x86_pkg_temp`、`acpitz`
+    pub temp_celsius: f32, // 摄氏度, 由 `temp` 文件的毫摄氏度换算而来
+}
+
+pub struct Thermal;
+
+impl Thermal {
+    const THERMAL_DIR: &'static str = "/sys/class/thermal";
+
+    /// 获取所有温控区的温度, 读不到 `temp` 的温控区会被跳过
+    pub(crate) fn get_thermal_zones() -> Vec<ThermalZone> {
+        let entries = match fs::read_dir(Self::THERMAL_DIR) {
+            Ok(entries) => entries,
+            Err(_) => return Vec::new(),
+        };
+
+        let mut zones: Vec<ThermalZone> = entries
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| Self::read_zone(entry.path()))
+            .collect();
+
+        zones.sort_by_key(|zone| zone.index);
+        zones
+    }
+
+    /// 读取单个 `thermal_zoneN` 目录
+    fn read_zone(path: std::path::PathBuf) -> Option<ThermalZone> {
+        let dir_name = path.file_name()?.to_str()?;
+        let index: usize = dir_name.strip_prefix("thermal_zone")?.parse().ok()?;
+
+        let temp_milli_celsius: f32 = fs::read_to_string(path.join("temp")).ok()?.trim().parse().ok()?;
+        let label = fs::read_to_string(path.join("type")).unwrap_or_default().trim().to_string();
+
+        Some(ThermalZone {
+            index,
+            label,
+            temp_celsius: temp_milli_celsius / 1000.0,
+        })
+    }
+}